@@ -0,0 +1,54 @@
+use anyhow::{Context, Result};
+use std::io::BufRead;
+
+/// Resolve the list of URLs to process. `--url-file` takes priority; otherwise a bare `-`
+/// positional URL reads newline-separated URLs from stdin; otherwise it's just the one
+/// positional URL. `url` is only absent when `--url-file` is given; clap enforces that.
+pub fn collect_urls(url: Option<&str>, url_file: Option<&str>) -> Result<Vec<String>> {
+    let urls = if let Some(path) = url_file {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read URL list from '{path}'"))?;
+        parse_lines(&contents)
+    } else {
+        let url = url.context("no URL given; pass a URL or use --url-file")?;
+        if url == "-" {
+            let stdin = std::io::stdin();
+            let mut lines = Vec::new();
+            for line in stdin.lock().lines() {
+                lines.push(line.context("failed to read URL from stdin")?);
+            }
+            parse_lines(&lines.join("\n"))
+        } else {
+            vec![url.to_string()]
+        }
+    };
+
+    if urls.is_empty() {
+        anyhow::bail!("no URLs to process");
+    }
+    Ok(urls)
+}
+
+fn parse_lines(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Expand `{host}`/`{index}` tokens in a `--screenshot`/`--output` path template so each URL
+/// in a batch lands in its own file. A template with no tokens behaves exactly as before.
+pub fn expand_template(template: &str, url: &str, index: usize) -> String {
+    template
+        .replace("{host}", &host_of(url))
+        .replace("{index}", &index.to_string())
+}
+
+fn host_of(url: &str) -> String {
+    let rest = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let authority_end = rest.find('/').unwrap_or(rest.len());
+    let authority = &rest[..authority_end];
+    authority.split(':').next().unwrap_or(authority).to_string()
+}
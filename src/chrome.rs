@@ -0,0 +1,83 @@
+use anyhow::{Context, Result};
+use headless_chrome::{Browser, LaunchOptions};
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+/// Everything needed to either launch a fresh Chrome process or attach to one already
+/// running, gathered from the `--chrome-*`/`--connect-*` flags.
+pub struct ChromeOptions<'a> {
+    pub chrome_flags: &'a [String],
+    pub window_size: Option<(u32, u32)>,
+    pub chrome_path: Option<&'a str>,
+    pub connect_ws: Option<&'a str>,
+    pub connect_port: Option<u16>,
+}
+
+/// Build the `Browser` for this run: attach to an already-running Chrome via its DevTools
+/// WebSocket endpoint if `--connect-ws`/`--connect-port` was given, otherwise launch a fresh
+/// process with the requested flags, binary, and window size.
+pub fn connect(opts: ChromeOptions) -> Result<Browser> {
+    if let Some(ws_url) = opts.connect_ws {
+        return Browser::connect(ws_url.to_string())
+            .with_context(|| format!("failed to attach to Chrome at '{ws_url}'"));
+    }
+
+    if let Some(port) = opts.connect_port {
+        let ws_url = discover_ws_url(port)?;
+        return Browser::connect(ws_url.clone())
+            .with_context(|| format!("failed to attach to Chrome at '{ws_url}'"));
+    }
+
+    let extra_args: Vec<&OsStr> = opts.chrome_flags.iter().map(OsStr::new).collect();
+
+    let launch_options = LaunchOptions {
+        headless: true,
+        window_size: opts.window_size,
+        path: opts.chrome_path.map(PathBuf::from),
+        args: extra_args,
+        ..Default::default()
+    };
+
+    Browser::new(launch_options).context("failed to launch Chrome")
+}
+
+/// Parse a `--window-size WxH` value like `"1280x800"`.
+pub fn parse_window_size(spec: &str) -> Result<(u32, u32)> {
+    let (width, height) = spec
+        .split_once('x')
+        .with_context(|| format!("invalid --window-size '{spec}', expected WxH (e.g. 1280x800)"))?;
+    Ok((
+        width
+            .parse()
+            .with_context(|| format!("invalid width in --window-size '{spec}'"))?,
+        height
+            .parse()
+            .with_context(|| format!("invalid height in --window-size '{spec}'"))?,
+    ))
+}
+
+/// Query Chrome's `/json/version` endpoint for the `webSocketDebuggerUrl` of the instance
+/// listening on `port`, the same handshake `--connect-port` users expect from other tools.
+fn discover_ws_url(port: u16) -> Result<String> {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port))
+        .with_context(|| format!("failed to reach Chrome DevTools on port {port}"))?;
+    let request =
+        format!("GET /json/version HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\nConnection: close\r\n\r\n");
+    stream.write_all(request.as_bytes())?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let body = response
+        .split_once("\r\n\r\n")
+        .map(|(_, body)| body)
+        .unwrap_or(response.as_str());
+
+    let json: serde_json::Value = serde_json::from_str(body)
+        .context("Chrome's /json/version response was not valid JSON")?;
+    json.get("webSocketDebuggerUrl")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .context("no webSocketDebuggerUrl in Chrome's /json/version response")
+}
@@ -0,0 +1,84 @@
+use anyhow::{Context, Result};
+use headless_chrome::protocol::cdp::Runtime::{CallArgument, CallFunctionOn};
+use headless_chrome::Tab;
+use serde_json::Value;
+
+/// Parse a `--arg name[:type]=value` flag into its JSON-typed value. Supported types are
+/// `int`, `float`, `bool`, and the default, `string`.
+pub fn parse_arg(spec: &str) -> Result<(String, Value)> {
+    let (name_and_type, raw_value) = spec
+        .split_once('=')
+        .with_context(|| format!("invalid --arg '{spec}', expected name[:type]=value"))?;
+    let (name, ty) = match name_and_type.split_once(':') {
+        Some((name, ty)) => (name, ty),
+        None => (name_and_type, "string"),
+    };
+
+    let value = match ty {
+        "int" => Value::from(
+            raw_value
+                .parse::<i64>()
+                .with_context(|| format!("invalid int value for --arg '{name}'"))?,
+        ),
+        "float" => Value::from(
+            raw_value
+                .parse::<f64>()
+                .with_context(|| format!("invalid float value for --arg '{name}'"))?,
+        ),
+        "bool" => Value::from(
+            raw_value
+                .parse::<bool>()
+                .with_context(|| format!("invalid bool value for --arg '{name}'"))?,
+        ),
+        "string" => Value::String(raw_value.to_string()),
+        other => anyhow::bail!("unknown --arg type '{other}' (expected int, float, bool, or string)"),
+    };
+
+    Ok((name.to_string(), value))
+}
+
+/// Run `expr` in the page context with `args` bound by name, marshalled through
+/// `Runtime.callFunctionOn` rather than string-interpolated into the expression, and return
+/// the result: JSON-serialized for objects/arrays, raw for scalars.
+pub fn run(tab: &Tab, expr: &str, args: Vec<(String, Value)>) -> Result<String> {
+    let window = tab.evaluate("window", false)?;
+    let object_id = window
+        .object_id
+        .context("failed to obtain a handle on the page's global object")?;
+
+    let param_names: Vec<&str> = args.iter().map(|(name, _)| name.as_str()).collect();
+    let function_declaration = format!("function({}) {{ return ({}); }}", param_names.join(", "), expr);
+
+    let arguments: Vec<CallArgument> = args
+        .into_iter()
+        .map(|(_, value)| CallArgument {
+            value: Some(value),
+            unserializable_value: None,
+            object_id: None,
+        })
+        .collect();
+
+    let result = tab.call_method(CallFunctionOn {
+        function_declaration,
+        object_id: Some(object_id),
+        arguments: Some(arguments),
+        silent: None,
+        return_by_value: Some(true),
+        generate_preview: None,
+        user_gesture: None,
+        await_promise: None,
+        execution_context_id: None,
+        object_group: None,
+        throw_on_side_effect: None,
+        unique_context_id: None,
+        serialization_options: None,
+    })?;
+
+    let value = result.result.value.unwrap_or(Value::Null);
+    Ok(match &value {
+        Value::String(s) => s.clone(),
+        Value::Null => "null".to_string(),
+        Value::Object(_) | Value::Array(_) => serde_json::to_string_pretty(&value)?,
+        _ => value.to_string(),
+    })
+}
@@ -0,0 +1,117 @@
+use anyhow::{Context, Result};
+use base64::Engine as _;
+use headless_chrome::browser::tab::{RequestInterceptor, RequestPausedDecision};
+use headless_chrome::browser::transport::{SessionId, Transport};
+use headless_chrome::protocol::cdp::Fetch::{
+    events::RequestPausedEvent, ContinueRequest, HeaderEntry, RequestPattern,
+};
+use headless_chrome::protocol::cdp::Network::ResourceType;
+use headless_chrome::Tab;
+use std::sync::Arc;
+
+/// Method, headers, and body to splice into the top-level document request before it leaves
+/// the browser, so `-X`/`-d`/`-H` behave like the real HTTP request they claim to be.
+pub struct RequestRewrite {
+    pub method: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+struct BodyInterceptor {
+    rewrite: RequestRewrite,
+    // Chrome gives a page's main frame the same id as the page's target.
+    main_frame_id: String,
+}
+
+impl RequestInterceptor for BodyInterceptor {
+    fn intercept(
+        &self,
+        _transport: Arc<Transport>,
+        _session_id: SessionId,
+        event: RequestPausedEvent,
+    ) -> RequestPausedDecision {
+        let params = &event.params;
+
+        // Only the top-level document request is ours to rewrite; anything else the page
+        // itself fires off (images, XHRs, sub-frames) goes through unmodified.
+        if params.resource_Type != ResourceType::Document || params.frame_id != self.main_frame_id {
+            return RequestPausedDecision::Continue(None);
+        }
+
+        let mut headers: Vec<HeaderEntry> = params
+            .request
+            .headers
+            .0
+            .as_ref()
+            .and_then(|value| value.as_object())
+            .map(|map| {
+                map.iter()
+                    .map(|(name, value)| HeaderEntry {
+                        name: name.clone(),
+                        value: value.as_str().unwrap_or_default().to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        for (name, value) in &self.rewrite.headers {
+            headers.retain(|h| !h.name.eq_ignore_ascii_case(name));
+            headers.push(HeaderEntry {
+                name: name.clone(),
+                value: value.clone(),
+            });
+        }
+
+        let post_data = self
+            .rewrite
+            .body
+            .as_ref()
+            .map(|body| base64::engine::general_purpose::STANDARD.encode(body));
+
+        RequestPausedDecision::Continue(Some(ContinueRequest {
+            request_id: params.request_id.clone(),
+            url: None,
+            method: Some(self.rewrite.method.clone()),
+            post_data,
+            headers: Some(headers),
+            intercept_response: None,
+        }))
+    }
+}
+
+/// Enable Fetch-domain interception on `tab` so the navigation that follows is rewritten
+/// according to `rewrite` before it's sent to the server. Only document requests are paused
+/// (sub-frame documents are resumed unmodified); sub-resources (images, scripts, XHRs, ...)
+/// go straight through without the pause-and-resume round trip.
+pub fn intercept_next_navigation(tab: &Tab, rewrite: RequestRewrite) -> Result<()> {
+    let main_frame_id = tab.get_target_id().clone();
+    tab.enable_request_interception(Arc::new(BodyInterceptor { rewrite, main_frame_id }))
+        .context("failed to enable Fetch-domain request interception")?;
+    tab.enable_fetch(Some(&[document_pattern()]), None)
+        .context("failed to enable the Fetch domain")?;
+    Ok(())
+}
+
+fn document_pattern() -> RequestPattern {
+    RequestPattern {
+        url_pattern: Some("*".to_string()),
+        resource_Type: Some(ResourceType::Document),
+        request_stage: None,
+    }
+}
+
+/// Resolve a `-d`/`--data-binary`/`--json` value, supporting curl's `@filename` syntax for
+/// reading the body from disk instead of the command line. Like curl, `-d` strips newlines
+/// from the body (`strip_newlines`) while `--data-binary`/`--json` send it exactly as read.
+pub fn resolve_body(spec: &str, strip_newlines: bool) -> Result<Vec<u8>> {
+    let raw = if let Some(path) = spec.strip_prefix('@') {
+        std::fs::read(path).with_context(|| format!("failed to read request body from '{path}'"))?
+    } else {
+        spec.as_bytes().to_vec()
+    };
+
+    if strip_newlines {
+        Ok(raw.into_iter().filter(|b| *b != b'\n' && *b != b'\r').collect())
+    } else {
+        Ok(raw)
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::{Context, Result};
+use headless_chrome::Tab;
+use std::collections::HashSet;
+
+const COLLECT_LINKS_JS: &str = r#"
+(() => {
+    const selectors = [
+        ["a", "href"],
+        ["img", "src"],
+        ["script", "src"],
+        ["link", "href"],
+        ["iframe", "src"],
+    ];
+    const urls = [];
+    for (const [tag, attr] of selectors) {
+        for (const el of document.querySelectorAll(tag)) {
+            const value = el[attr];
+            if (value) urls.push(value);
+        }
+    }
+    return JSON.stringify(urls);
+})()
+"#;
+
+/// Collect every `href`/`src` on the rendered page (anchors, images, scripts, stylesheets,
+/// iframes), already resolved to absolute URLs by the DOM and de-duplicated. URLs are grouped
+/// by element type in that order (anchors first, iframes last), each group in document order.
+/// When `local_only` is set, URLs whose origin doesn't match the fetched page are dropped.
+pub fn extract_links(tab: &Tab, local_only: bool) -> Result<Vec<String>> {
+    let result = tab.evaluate(COLLECT_LINKS_JS, false)?;
+    let raw = result
+        .value
+        .context("no value returned while collecting links")?;
+    let raw = raw.as_str().context("expected a JSON string of links")?;
+    let urls: Vec<String> = serde_json::from_str(raw)?;
+
+    let origin = if local_only {
+        let origin_result = tab.evaluate("location.origin", false)?;
+        origin_result
+            .value
+            .and_then(|v| v.as_str().map(str::to_string))
+    } else {
+        None
+    };
+
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+    for url in urls {
+        if let Some(origin) = &origin {
+            if url_origin(&url) != *origin {
+                continue;
+            }
+        }
+        if seen.insert(url.clone()) {
+            links.push(url);
+        }
+    }
+    Ok(links)
+}
+
+/// Cheap scheme://host[:port] extraction; the DOM has already resolved URLs to absolute
+/// form, so this just needs to match `location.origin`'s own format.
+fn url_origin(url: &str) -> String {
+    match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let host_end = rest.find('/').unwrap_or(rest.len());
+            format!("{scheme}://{}", &rest[..host_end])
+        }
+        None => String::new(),
+    }
+}
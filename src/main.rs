@@ -1,10 +1,20 @@
 use anyhow::Result;
 use clap::{Parser, ValueEnum};
 use colored::*;
-use headless_chrome::{Browser, LaunchOptions};
+use headless_chrome::Browser;
 use serde_json::Value;
+use std::sync::Arc;
 use std::time::Duration;
 
+mod batch;
+mod chrome;
+mod evaluate;
+mod fetch_intercept;
+mod links;
+mod markdown;
+mod network;
+mod screenshot;
+
 #[derive(Parser, Debug)]
 #[command(name = "jurl")]
 #[command(version = "1.0.0")]
@@ -46,9 +56,45 @@ EXAMPLES:
   # Include response headers in output
   jurl -i https://example.com
 
-  # POST request with data (limited support)
+  # POST request with data
   jurl -X POST -d \"key=value\" https://example.com/api
 
+  # POST a JSON body with the right Content-Type
+  jurl -X POST --json '{\"key\": \"value\"}' https://example.com/api
+
+  # Send a body read from a file, like curl's @filename
+  jurl -X PUT --data-binary @payload.json https://example.com/api
+
+  # List every link on a JS-rendered page, same-origin only
+  jurl --links --local-links-only https://example.com
+
+  # Screenshot a batch of URLs from a file, 4 at a time, one file per URL
+  jurl --url-file urls.txt --concurrency 4 --screenshot \"{host}-{index}.png\"
+
+  # Get LLM-ready article text from a JS-heavy page
+  jurl --format markdown --main-content https://example.com/blog/post
+
+  # Launch through a proxy with a custom window size
+  jurl --chrome-flag=--proxy-server=http://localhost:8080 --window-size 1280x800 https://example.com
+
+  # Attach to a Chrome already running with --remote-debugging-port=9222
+  jurl --connect-port 9222 https://example.com
+
+  # Query specific DOM state instead of dumping the whole page
+  jurl --evaluate \"document.querySelectorAll(sel).length\" --arg sel=.price https://example.com
+
+  # Seed a session cookie and save everything the page set afterward
+  jurl -b \"session=abc123\" -c cookies.txt --incognito https://example.com
+
+  # Compact JPEG thumbnail of just the viewport
+  jurl --screenshot thumb.jpg --screenshot-format jpeg --screenshot-quality 70 --no-full-page https://example.com
+
+  # Clip a screenshot to a single component
+  jurl --screenshot widget.png --screenshot-selector \"#pricing-table\" https://example.com
+
+  # Archive a page as PDF
+  jurl --pdf page.pdf https://example.com
+
 DIFFERENCES FROM CURL:
   • Executes JavaScript (curl doesn't)
   • Renders full DOM (curl only gets initial HTML)
@@ -58,9 +104,20 @@ DIFFERENCES FROM CURL:
 NOTE: The browser is automatically downloaded on first run and cached locally.
       No manual browser installation or configuration is required.")]
 struct Args {
-    /// URL to fetch (required)
-    #[arg(help = "The URL to fetch. Must include protocol (http:// or https://).")]
-    url: String,
+    /// URL to fetch (required unless --url-file is given)
+    #[arg(
+        required_unless_present = "url_file",
+        help = "The URL to fetch. Must include protocol (http:// or https://). Pass '-' to read a newline-separated URL list from stdin. Not required when --url-file is given."
+    )]
+    url: Option<String>,
+
+    /// Read URLs from a file instead of the command line
+    #[arg(long = "url-file", help = "Read a newline-separated list of URLs from this file and process them all, sharing one browser instance.")]
+    url_file: Option<String>,
+
+    /// Number of URLs to process concurrently in batch mode
+    #[arg(long = "concurrency", default_value = "1", help = "Maximum number of URLs to process at once when given multiple URLs via '-' or --url-file.")]
+    concurrency: usize,
 
     /// HTTP method to use
     #[arg(short = 'X', long = "request", default_value = "GET", help = "Specify request method to use (GET, POST, etc.). Default is GET.")]
@@ -87,9 +144,17 @@ struct Args {
     headers: Vec<String>,
 
     /// Data to send with POST request
-    #[arg(short = 'd', long = "data")]
+    #[arg(short = 'd', long = "data", help = "Request body to send with -X POST/PUT/PATCH/DELETE. Prefix with '@' to read the body from a file, like curl.")]
     data: Option<String>,
 
+    /// Data to send as-is, without curl's newline stripping
+    #[arg(long = "data-binary", help = "Like --data, but the body is sent exactly as given (or read via '@file'). Use for binary or already-formatted payloads.")]
+    data_binary: Option<String>,
+
+    /// Data to send with Content-Type: application/json
+    #[arg(long = "json", help = "Request body sent verbatim with 'Content-Type: application/json'. Prefix with '@' to read the body from a file.")]
+    json: Option<String>,
+
     /// Wait for selector before capturing content
     #[arg(long = "wait-for-selector", help = "Wait for a specific CSS selector to appear before capturing content. Useful for dynamic content.")]
     wait_for_selector: Option<String>,
@@ -103,9 +168,21 @@ struct Args {
     screenshot: Option<String>,
 
     /// Output format
-    #[arg(long = "format", value_enum, default_value = "html", help = "Output format: html (raw HTML), text (text only, no tags), json (attempt to parse as JSON).")]
+    #[arg(long = "format", value_enum, default_value = "html", help = "Output format: html (raw HTML), text (text only, no tags), json (attempt to parse as JSON), links (one URL per line), markdown (readable article Markdown).")]
     format: OutputFormat,
 
+    /// Narrow markdown output to the page's main content
+    #[arg(long = "main-content", help = "Used with --format markdown: run a Readability-style heuristic to keep only the main content container, dropping boilerplate chrome.")]
+    main_content: bool,
+
+    /// Extract every link from the rendered page instead of printing content
+    #[arg(long = "links", help = "List every href/src on the rendered page (anchors, images, scripts, stylesheets, iframes), one absolute URL per line. Shorthand for --format links.")]
+    links: bool,
+
+    /// Keep only same-origin links
+    #[arg(long = "local-links-only", requires = "links", help = "Used with --links: keep only URLs whose origin (scheme+host+port) matches the fetched page.")]
+    local_links_only: bool,
+
     /// Show only response body (no headers)
     #[arg(short = 's', long = "silent")]
     silent: bool,
@@ -113,6 +190,88 @@ struct Args {
     /// User agent string
     #[arg(short = 'A', long = "user-agent", help = "Send User-Agent header to server. Useful for accessing sites that block automated tools.")]
     user_agent: Option<String>,
+
+    /// Extra flags to pass through to the Chrome process (repeatable)
+    #[arg(long = "chrome-flag", help = "Append an arbitrary switch to the spawned Chrome process, e.g. --chrome-flag=--no-sandbox. Can be used multiple times.")]
+    chrome_flag: Vec<String>,
+
+    /// Browser window size as WxH
+    #[arg(long = "window-size", help = "Override the browser window size, e.g. 1280x800. Defaults to 1920x1080.")]
+    window_size: Option<String>,
+
+    /// Path to a specific Chrome/Chromium binary
+    #[arg(long = "chrome-path", help = "Use this Chrome/Chromium binary instead of the auto-downloaded one.")]
+    chrome_path: Option<String>,
+
+    /// Attach to a running Chrome over its DevTools WebSocket URL
+    #[arg(long = "connect-ws", conflicts_with = "connect_port", help = "Attach to an already-running Chrome instance via its DevTools WebSocket URL instead of launching a new one.")]
+    connect_ws: Option<String>,
+
+    /// Attach to a running Chrome via its DevTools port
+    #[arg(long = "connect-port", conflicts_with = "connect_ws", help = "Attach to an already-running Chrome instance listening on this DevTools port (e.g. 9222) instead of launching a new one.")]
+    connect_port: Option<u16>,
+
+    /// Evaluate a JavaScript expression on the page and print its result
+    #[arg(long = "evaluate", help = "Run a JavaScript expression in the page context after rendering and print its result instead of page content. Use --arg to bind variables into it.")]
+    evaluate: Option<String>,
+
+    /// Typed argument to bind into --evaluate (repeatable)
+    #[arg(long = "arg", help = "Bind a typed variable for --evaluate: name=value (string), name:int=value, name:float=value, or name:bool=value. Can be used multiple times.")]
+    arg: Vec<String>,
+
+    /// Cookie to send before navigation (can be used multiple times)
+    #[arg(short = 'b', long = "cookie", help = "Seed a cookie before navigation. Format: 'name=value'. Can be used multiple times.")]
+    cookie: Vec<String>,
+
+    /// Dump cookies to a file after load
+    #[arg(short = 'c', long = "cookie-jar", help = "Write all cookies visible after load to this file in Netscape cookie-file format.")]
+    cookie_jar: Option<String>,
+
+    /// Run each fetch in a fresh browser context
+    #[arg(long = "incognito", help = "Run each fetch in a fresh, isolated browser context so cookies and storage don't leak between batch URLs.")]
+    incognito: bool,
+
+    /// Image format for --screenshot
+    #[arg(long = "screenshot-format", value_enum, default_value = "png", help = "Image format for --screenshot: png, jpeg, or webp.")]
+    screenshot_format: ScreenshotFormat,
+
+    /// Quality for lossy --screenshot formats
+    #[arg(long = "screenshot-quality", help = "Quality (0-100) for --screenshot-format jpeg/webp. Ignored for png.")]
+    screenshot_quality: Option<u32>,
+
+    /// Capture the entire scrollable page
+    #[arg(long = "full-page", overrides_with = "no_full_page", help = "Capture the entire scrollable page instead of just the viewport. Default.")]
+    full_page: bool,
+
+    /// Capture only the visible viewport
+    #[arg(long = "no-full-page", overrides_with = "full_page", help = "Capture only the visible viewport instead of the entire scrollable page.")]
+    no_full_page: bool,
+
+    /// Clip --screenshot to a single element
+    #[arg(long = "screenshot-selector", help = "Clip the --screenshot capture to the bounding box of the first element matching this CSS selector.")]
+    screenshot_selector: Option<String>,
+
+    /// Export the rendered page as a PDF
+    #[arg(long = "pdf", help = "Export the rendered page as a PDF to this file, via Page.printToPDF.")]
+    pdf: Option<String>,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+enum ScreenshotFormat {
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl ScreenshotFormat {
+    fn to_cdp(&self) -> headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption {
+        use headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption;
+        match self {
+            ScreenshotFormat::Png => CaptureScreenshotFormatOption::Png,
+            ScreenshotFormat::Jpeg => CaptureScreenshotFormatOption::Jpeg,
+            ScreenshotFormat::Webp => CaptureScreenshotFormatOption::Webp,
+        }
+    }
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -120,26 +279,76 @@ enum OutputFormat {
     Html,
     Text,
     Json,
+    Links,
+    Markdown,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
+    let urls = batch::collect_urls(args.url.as_deref(), args.url_file.as_deref())?;
+
+    // Launch one browser (or attach to a running one) and reuse it across every URL;
+    // relaunching Chrome per URL is the dominant cost in batch mode.
+    let window_size = match &args.window_size {
+        Some(spec) => Some(chrome::parse_window_size(spec)?),
+        None => Some((1920, 1080)),
+    };
+    let browser = Arc::new(chrome::connect(chrome::ChromeOptions {
+        chrome_flags: &args.chrome_flag,
+        window_size,
+        chrome_path: args.chrome_path.as_deref(),
+        connect_ws: args.connect_ws.as_deref(),
+        connect_port: args.connect_port,
+    })?);
+    let args = Arc::new(args);
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(args.concurrency.max(1)));
+
+    let mut tasks = Vec::with_capacity(urls.len());
+    for (index, url) in urls.into_iter().enumerate() {
+        let browser = Arc::clone(&browser);
+        let args = Arc::clone(&args);
+        let semaphore = Arc::clone(&semaphore);
+        tasks.push(tokio::spawn(async move {
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("semaphore is never closed");
+            tokio::task::spawn_blocking(move || process_url(&browser, &args, &url, index))
+                .await
+                .expect("worker thread panicked")
+        }));
+    }
 
+    let mut had_error = false;
+    for task in tasks {
+        if let Err(err) = task.await.expect("worker task panicked") {
+            eprintln!("{}", format!("jurl: {err:#}").red());
+            had_error = true;
+        }
+    }
+
+    if had_error {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Fetch a single URL: navigate (optionally rewriting method/headers/body), wait for content,
+/// then capture a screenshot or emit the page in the requested format.
+fn process_url(browser: &Browser, args: &Args, url: &str, index: usize) -> Result<()> {
     if args.verbose {
-        eprintln!("{}", format!("* Connecting to {}...", args.url).cyan());
+        eprintln!("{}", format!("* Connecting to {url}...").cyan());
     }
 
-    // Launch browser with options
-    let launch_options = LaunchOptions {
-        headless: true,
-        window_size: Some((1920, 1080)),
-        ..Default::default()
+    // --incognito isolates cookies/storage per URL by giving it its own browser context
+    // instead of the shared default one.
+    let tab = if args.incognito {
+        browser.new_context()?.new_tab()?
+    } else {
+        browser.new_tab()?
     };
 
-    let browser = Browser::new(launch_options)?;
-    let tab = browser.new_tab()?;
-
     // Set user agent if provided
     if let Some(user_agent) = &args.user_agent {
         tab.set_user_agent(user_agent, None, None)?;
@@ -149,31 +358,58 @@ async fn main() -> Result<()> {
     tab.set_default_timeout(Duration::from_secs(args.timeout));
 
     if args.verbose {
-        eprintln!("{}", format!("* Navigating to {}...", args.url).cyan());
+        eprintln!("{}", format!("* Navigating to {url}...").cyan());
     }
 
-    // Navigate to URL
-    match args.method.to_uppercase().as_str() {
-        "GET" => {
-            tab.navigate_to(&args.url)?;
-        }
-        "POST" => {
-            // For POST requests, we navigate first then execute JS to submit data
-            tab.navigate_to(&args.url)?;
-            
-            if let Some(data) = &args.data {
-                if args.verbose {
-                    eprintln!("{}", format!("* Sending POST data: {}", data).cyan());
-                }
-                // Note: Full POST implementation would require more sophisticated handling
-            }
+    // Resolve the request body (if any) from -d/--data-binary/--json, supporting curl's
+    // '@filename' syntax for reading it off disk.
+    let (body, json_content_type) = if let Some(json) = &args.json {
+        (Some(fetch_intercept::resolve_body(json, false)?), true)
+    } else if let Some(data_binary) = &args.data_binary {
+        (Some(fetch_intercept::resolve_body(data_binary, false)?), false)
+    } else if let Some(data) = &args.data {
+        (Some(fetch_intercept::resolve_body(data, true)?), false)
+    } else {
+        (None, false)
+    };
+
+    let method = args.method.to_uppercase();
+
+    // Only the Fetch domain lets us actually rewrite the method, headers, and body of the
+    // navigation request itself; skip it for a plain GET with no extra headers.
+    if method != "GET" || !args.headers.is_empty() || body.is_some() {
+        let mut headers: Vec<(String, String)> = args
+            .headers
+            .iter()
+            .filter_map(|h| h.split_once(':'))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect();
+        if json_content_type {
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case("content-type"));
+            headers.push(("Content-Type".to_string(), "application/json".to_string()));
         }
-        _ => {
-            eprintln!("Unsupported method: {}", args.method);
-            std::process::exit(1);
+
+        if args.verbose {
+            eprintln!("{}", format!("* Rewriting request as {method} via Fetch interception").cyan());
         }
+
+        fetch_intercept::intercept_next_navigation(
+            &tab,
+            fetch_intercept::RequestRewrite {
+                method,
+                headers,
+                body,
+            },
+        )?;
     }
 
+    // Seed cookies and start capturing the real response before navigating, so -i/-c have
+    // something true to report afterward.
+    network::set_cookies(&tab, url, &args.cookie)?;
+    let response_info = network::capture_main_response(&tab)?;
+
+    tab.navigate_to(url)?;
+
     // Wait for specific selector if provided
     if let Some(selector) = &args.wait_for_selector {
         if args.verbose {
@@ -187,24 +423,76 @@ async fn main() -> Result<()> {
         std::thread::sleep(Duration::from_secs(2));
     }
 
-    // Take screenshot if requested
+    if let Some(cookie_jar_path) = &args.cookie_jar {
+        let cookie_jar_path = batch::expand_template(cookie_jar_path, url, index);
+        if args.verbose {
+            eprintln!("{}", format!("* Writing cookie jar to: {cookie_jar_path}").cyan());
+        }
+        network::dump_cookie_jar(&tab, &cookie_jar_path)?;
+    }
+
+    // Take a screenshot and/or export a PDF if requested; neither produces page content, so
+    // both return early like the rest of the capture modes.
+    let mut captured = false;
+
     if let Some(screenshot_path) = &args.screenshot {
+        let screenshot_path = batch::expand_template(screenshot_path, url, index);
         if args.verbose {
-            eprintln!("{}", format!("* Taking screenshot to: {}", screenshot_path).cyan());
+            eprintln!("{}", format!("* Taking screenshot to: {screenshot_path}").cyan());
         }
-        let screenshot_data = tab.capture_screenshot(
-            headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption::Png,
-            None,
-            None,
-            true,
+        let clip = match &args.screenshot_selector {
+            Some(selector) => Some(screenshot::clip_for_selector(&tab, selector)?),
+            None => None,
+        };
+        let screenshot_data = screenshot::capture(
+            &tab,
+            args.screenshot_format.to_cdp(),
+            args.screenshot_quality,
+            clip,
+            !args.no_full_page,
         )?;
-        std::fs::write(screenshot_path, screenshot_data)?;
-        println!("Screenshot saved to: {}", screenshot_path);
+        std::fs::write(&screenshot_path, screenshot_data)?;
+        println!("Screenshot saved to: {screenshot_path}");
+        captured = true;
+    }
+
+    if let Some(pdf_path) = &args.pdf {
+        let pdf_path = batch::expand_template(pdf_path, url, index);
+        if args.verbose {
+            eprintln!("{}", format!("* Exporting PDF to: {pdf_path}").cyan());
+        }
+        screenshot::export_pdf(&tab, &pdf_path)?;
+        println!("PDF saved to: {pdf_path}");
+        captured = true;
+    }
+
+    if captured {
         return Ok(());
     }
 
+    // Evaluate a JS expression and print its result instead of page content
+    if let Some(expr) = &args.evaluate {
+        let eval_args = args
+            .arg
+            .iter()
+            .map(|spec| evaluate::parse_arg(spec))
+            .collect::<Result<Vec<_>>>()?;
+        let result = evaluate::run(&tab, expr, eval_args)?;
+        if !args.silent {
+            println!("{result}");
+        }
+        return Ok(());
+    }
+
+    // --links is shorthand for --format links
+    let format = if args.links {
+        OutputFormat::Links
+    } else {
+        args.format.clone()
+    };
+
     // Get page content based on format
-    let content = match args.format {
+    let content = match format {
         OutputFormat::Html => {
             // Get the full HTML content
             tab.get_content()?
@@ -225,24 +513,42 @@ async fn main() -> Result<()> {
                 text
             }
         }
+        OutputFormat::Links => {
+            let urls = links::extract_links(&tab, args.local_links_only)?;
+            urls.join("\n")
+        }
+        OutputFormat::Markdown => markdown::render(&tab, args.main_content)?,
     };
 
     // Handle output
     if args.include_headers && !args.silent {
-        // Get response info (simplified version)
-        println!("{}", "HTTP/1.1 200 OK".green());
-        println!("{}", format!("Content-Length: {}", content.len()).green());
-        println!("{}", "Content-Type: text/html; charset=utf-8".green());
+        match response_info.lock().unwrap().clone() {
+            Some(info) => {
+                println!(
+                    "{}",
+                    format!("HTTP/1.1 {} {}", info.status, info.status_text).green()
+                );
+                for (name, value) in &info.headers {
+                    println!("{}", format!("{name}: {value}").green());
+                }
+            }
+            None => {
+                // Network.responseReceived never fired for the top-level document; fall back
+                // honestly.
+                println!("{}", format!("Content-Length: {}", content.len()).green());
+            }
+        }
         println!();
     }
 
     if let Some(output_file) = &args.output {
+        let output_file = batch::expand_template(output_file, url, index);
         if args.verbose {
-            eprintln!("{}", format!("* Writing output to: {}", output_file).cyan());
+            eprintln!("{}", format!("* Writing output to: {output_file}").cyan());
         }
-        std::fs::write(output_file, content)?;
+        std::fs::write(&output_file, content)?;
         if !args.silent {
-            println!("Output saved to: {}", output_file);
+            println!("Output saved to: {output_file}");
         }
     } else if !args.silent {
         println!("{}", content);
@@ -0,0 +1,158 @@
+use anyhow::Context;
+use anyhow::Result;
+use headless_chrome::Tab;
+
+/// Convert the rendered DOM into readable Markdown. When `main_content` is set, a
+/// Readability-style heuristic (text-length vs. link-density) first narrows the page down to
+/// its main content container, so boilerplate chrome doesn't drown out the article.
+pub fn render(tab: &Tab, main_content: bool) -> Result<String> {
+    let script = RENDER_MARKDOWN_JS.replace("__MAIN_CONTENT__", if main_content { "true" } else { "false" });
+    let result = tab.evaluate(&script, false)?;
+    let value = result
+        .value
+        .context("no value returned while rendering markdown")?;
+    Ok(value.as_str().unwrap_or("").to_string())
+}
+
+const RENDER_MARKDOWN_JS: &str = r##"
+(() => {
+    const MAIN_CONTENT = __MAIN_CONTENT__;
+
+    for (const selector of ["script", "style", "nav", "footer", "noscript", "template"]) {
+        document.querySelectorAll(selector).forEach((el) => el.remove());
+    }
+
+    function textLength(el) {
+        return (el.innerText || "").trim().length;
+    }
+
+    function linkDensity(el) {
+        const length = textLength(el);
+        if (length === 0) return 1;
+        const linkText = Array.from(el.querySelectorAll("a"))
+            .reduce((sum, a) => sum + (a.innerText || "").trim().length, 0);
+        return linkText / length;
+    }
+
+    function pickMainContent() {
+        const candidates = document.querySelectorAll("article, main, section, div");
+        let best = document.body;
+        let bestScore = -Infinity;
+        for (const el of candidates) {
+            const length = textLength(el);
+            if (length < 140) continue;
+            const score = length * (1 - linkDensity(el));
+            if (score > bestScore) {
+                bestScore = score;
+                best = el;
+            }
+        }
+        return best;
+    }
+
+    const root = MAIN_CONTENT ? pickMainContent() : document.body;
+
+    function renderInline(node) {
+        let out = "";
+        for (const child of node.childNodes) {
+            if (child.nodeType === Node.TEXT_NODE) {
+                out += child.textContent;
+                continue;
+            }
+            if (child.nodeType !== Node.ELEMENT_NODE) continue;
+            const tag = child.tagName.toLowerCase();
+            if (tag === "strong" || tag === "b") {
+                out += `**${renderInline(child)}**`;
+            } else if (tag === "em" || tag === "i") {
+                out += `*${renderInline(child)}*`;
+            } else if (tag === "code") {
+                out += `\`${child.textContent}\``;
+            } else if (tag === "a") {
+                out += `[${renderInline(child)}](${child.href})`;
+            } else if (tag === "img") {
+                out += `![${child.alt || ""}](${child.src})`;
+            } else if (tag === "br") {
+                out += "\n";
+            } else {
+                out += renderInline(child);
+            }
+        }
+        return out.trim();
+    }
+
+    function renderList(el, ordered, depth) {
+        const indent = "  ".repeat(depth);
+        let out = "";
+        let index = 1;
+        for (const item of el.children) {
+            if (item.tagName.toLowerCase() !== "li") continue;
+            const marker = ordered ? `${index}.` : "-";
+            out += `${indent}${marker} ${renderInline(item)}\n`;
+            index += 1;
+        }
+        return out;
+    }
+
+    function renderTable(el) {
+        const rows = Array.from(el.querySelectorAll("tr"));
+        if (rows.length === 0) return "";
+        let out = "";
+        rows.forEach((row, rowIndex) => {
+            const cells = Array.from(row.querySelectorAll("th, td")).map((cell) =>
+                renderInline(cell).replace(/\|/g, "\\|")
+            );
+            out += `| ${cells.join(" | ")} |\n`;
+            if (rowIndex === 0) {
+                out += `| ${cells.map(() => "---").join(" | ")} |\n`;
+            }
+        });
+        return out;
+    }
+
+    function renderBlock(el) {
+        const tag = el.tagName.toLowerCase();
+        if (/^h[1-6]$/.test(tag)) {
+            const level = Number(tag[1]);
+            return `${"#".repeat(level)} ${renderInline(el)}\n\n`;
+        }
+        if (tag === "p") {
+            return `${renderInline(el)}\n\n`;
+        }
+        if (tag === "ul") {
+            return `${renderList(el, false, 0)}\n`;
+        }
+        if (tag === "ol") {
+            return `${renderList(el, true, 0)}\n`;
+        }
+        if (tag === "pre") {
+            const code = el.querySelector("code");
+            return `\`\`\`\n${(code || el).textContent.trim()}\n\`\`\`\n\n`;
+        }
+        if (tag === "blockquote") {
+            return (
+                renderInline(el)
+                    .split("\n")
+                    .map((line) => `> ${line}`)
+                    .join("\n") + "\n\n"
+            );
+        }
+        if (tag === "table") {
+            return `${renderTable(el)}\n`;
+        }
+        if (tag === "hr") {
+            return "---\n\n";
+        }
+        let out = "";
+        for (const child of el.children) {
+            out += renderBlock(child);
+        }
+        if (out === "") {
+            const text = renderInline(el);
+            if (text) out += `${text}\n\n`;
+        }
+        return out;
+    }
+
+    return renderBlock(root).replace(/\n{3,}/g, "\n\n").trim();
+})()
+"##;
@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+use headless_chrome::protocol::cdp::types::Event;
+use headless_chrome::protocol::cdp::Network::{CookieParam, Enable, ResourceType};
+use headless_chrome::Tab;
+use std::sync::{Arc, Mutex};
+
+/// Real status code, status text, and headers for the top-level document response, captured
+/// from CDP `Network.responseReceived` instead of the fabricated `HTTP/1.1 200 OK` this tool
+/// used to print for `-i`. The HTTP protocol version is not captured.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseInfo {
+    pub status: u16,
+    pub status_text: String,
+    pub headers: Vec<(String, String)>,
+}
+
+/// Enable the Network domain on `tab` and capture the most recent top-level document response
+/// seen on it, retrievable later through the returned handle. A script or meta-refresh
+/// redirect loads a new top-level document with its own response, so we keep overwriting
+/// rather than stopping at the first hit -- once navigation settles, the handle holds the
+/// response that was actually rendered. HTTP redirect hops don't fire this event at all.
+pub fn capture_main_response(tab: &Tab) -> Result<Arc<Mutex<Option<ResponseInfo>>>> {
+    let captured = Arc::new(Mutex::new(None));
+    let sink = Arc::clone(&captured);
+    // Chrome gives a page's main frame the same id as the page's target.
+    let main_frame_id = tab.get_target_id().clone();
+
+    tab.call_method(Enable {
+        max_total_buffer_size: None,
+        max_resource_buffer_size: None,
+        max_post_data_size: None,
+        report_direct_socket_traffic: None,
+        enable_durable_messages: None,
+    })?;
+    tab.add_event_listener(Arc::new(move |event: &Event| {
+        let Event::NetworkResponseReceived(event) = event else {
+            return;
+        };
+        if event.params.Type != ResourceType::Document
+            || event.params.frame_id.as_deref() != Some(main_frame_id.as_str())
+        {
+            // Images/XHRs/sub-frames/etc. the page itself triggers; only the top-level document
+            // is ours.
+            return;
+        }
+        let response = &event.params.response;
+        let headers = response
+            .headers
+            .0
+            .as_ref()
+            .and_then(|value| value.as_object())
+            .map(|map| {
+                map.iter()
+                    .map(|(name, value)| (name.clone(), value.as_str().unwrap_or_default().to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        *sink.lock().unwrap() = Some(ResponseInfo {
+            status: response.status as u16,
+            status_text: response.status_text.clone(),
+            headers,
+        });
+    }))?;
+
+    Ok(captured)
+}
+
+/// Seed cookies before navigation from repeated `-b`/`--cookie` values in `name=value` form.
+pub fn set_cookies(tab: &Tab, url: &str, cookie_specs: &[String]) -> Result<()> {
+    if cookie_specs.is_empty() {
+        return Ok(());
+    }
+    let cookies: Vec<CookieParam> = cookie_specs
+        .iter()
+        .filter_map(|spec| spec.split_once('='))
+        .map(|(name, value)| CookieParam {
+            name: name.trim().to_string(),
+            value: value.trim().to_string(),
+            url: Some(url.to_string()),
+            domain: None,
+            path: None,
+            secure: None,
+            http_only: None,
+            same_site: None,
+            expires: None,
+            priority: None,
+            same_party: None,
+            source_scheme: None,
+            source_port: None,
+            partition_key: None,
+        })
+        .collect();
+    tab.set_cookies(cookies)
+        .context("failed to seed cookies before navigation")
+}
+
+/// Dump every cookie visible to `tab` to `path` in Netscape cookie-file format, the format
+/// curl's `-c`/`--cookie-jar` produces.
+pub fn dump_cookie_jar(tab: &Tab, path: &str) -> Result<()> {
+    let cookies = tab.get_cookies().context("failed to read cookies")?;
+    let mut out = String::from("# Netscape HTTP Cookie File\n");
+    for cookie in cookies {
+        out.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            cookie.domain,
+            if cookie.domain.starts_with('.') { "TRUE" } else { "FALSE" },
+            cookie.path,
+            if cookie.secure { "TRUE" } else { "FALSE" },
+            cookie.expires as i64,
+            cookie.name,
+            cookie.value,
+        ));
+    }
+    std::fs::write(path, out).with_context(|| format!("failed to write cookie jar to '{path}'"))
+}
@@ -0,0 +1,98 @@
+use anyhow::Context;
+use anyhow::Result;
+use headless_chrome::protocol::cdp::Emulation::SetDeviceMetricsOverride;
+use headless_chrome::protocol::cdp::Page::{CaptureScreenshotFormatOption, Viewport};
+use headless_chrome::Tab;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// Compute the CDP clip rect for `selector`'s bounding box, so a screenshot can be clipped to
+/// a single element instead of the whole page.
+pub fn clip_for_selector(tab: &Tab, selector: &str) -> Result<Viewport> {
+    let js = format!(
+        "(() => {{ const el = document.querySelector({selector:?}); if (!el) return null; \
+         const r = el.getBoundingClientRect(); \
+         return JSON.stringify({{x: r.x, y: r.y, width: r.width, height: r.height}}); }})()"
+    );
+    let result = tab.evaluate(&js, false)?;
+    let raw = result
+        .value
+        .context("no value returned while locating --screenshot-selector")?;
+    let raw = raw
+        .as_str()
+        .context("expected a JSON string from the bounding-rect script")?;
+    let rect: Option<Rect> = serde_json::from_str(raw)?;
+    let rect = rect
+        .with_context(|| format!("no element matched --screenshot-selector '{selector}'"))?;
+
+    Ok(Viewport {
+        x: rect.x,
+        y: rect.y,
+        width: rect.width,
+        height: rect.height,
+        scale: 1.0,
+    })
+}
+
+/// Capture a screenshot of `tab` in `format`, optionally clipped to `clip` and, when
+/// `full_page` is set and there's no clip, expanded to the page's entire scrollable size
+/// first instead of just the visible viewport.
+pub fn capture(
+    tab: &Tab,
+    format: CaptureScreenshotFormatOption,
+    quality: Option<u32>,
+    clip: Option<Viewport>,
+    full_page: bool,
+) -> Result<Vec<u8>> {
+    if full_page && clip.is_none() {
+        let (width, height) = page_dimensions(tab)?;
+        tab.call_method(SetDeviceMetricsOverride {
+            width,
+            height,
+            device_scale_factor: 1.0,
+            mobile: false,
+            scale: None,
+            screen_width: None,
+            screen_height: None,
+            position_x: None,
+            position_y: None,
+            dont_set_visible_size: None,
+            screen_orientation: None,
+            viewport: None,
+            display_feature: None,
+            device_posture: None,
+        })
+        .context("failed to expand the viewport for a full-page screenshot")?;
+    }
+
+    tab.capture_screenshot(format, quality, clip, true)
+        .context("failed to capture screenshot")
+}
+
+/// Export the rendered page to `path` as a PDF via `Page.printToPDF`.
+pub fn export_pdf(tab: &Tab, path: &str) -> Result<()> {
+    let pdf_data = tab.print_to_pdf(None).context("failed to render PDF")?;
+    std::fs::write(path, pdf_data).with_context(|| format!("failed to write PDF to '{path}'"))
+}
+
+fn page_dimensions(tab: &Tab) -> Result<(u32, u32)> {
+    let js = "JSON.stringify({w: document.documentElement.scrollWidth, h: document.documentElement.scrollHeight})";
+    let result = tab.evaluate(js, false)?;
+    let raw = result
+        .value
+        .context("no value returned while measuring page size")?;
+    let raw = raw
+        .as_str()
+        .context("expected a JSON string of page dimensions")?;
+    let dims: serde_json::Value = serde_json::from_str(raw)?;
+    let width = dims["w"].as_u64().context("missing page width")? as u32;
+    let height = dims["h"].as_u64().context("missing page height")? as u32;
+    Ok((width, height))
+}